@@ -1,5 +1,12 @@
 use bigdecimal::BigDecimal;
-use std::{collections::BTreeMap, fmt::Display};
+use num_bigint::BigInt;
+use num_complex::Complex;
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Write},
+};
 
 use crate::{
     scinot_parsing::dec_in_scientific_notation, scinot_parsing::max_precision, syntax::Name,
@@ -9,56 +16,376 @@ use crate::{
 pub struct Value {
     pub kind: ValueKind,
     pub unit: Unit,
+    pub format: NumberFormat,
+}
+
+/// Controls how integer-valued [`Value`]s are rendered by `Display`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum NumberFormat {
+    Decimal,
+    Binary,
+    Octal,
+    Hex,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat::Decimal
+    }
+}
+
+impl NumberFormat {
+    fn radix(self) -> Option<u32> {
+        match self {
+            NumberFormat::Decimal => None,
+            NumberFormat::Binary => Some(2),
+            NumberFormat::Octal => Some(8),
+            NumberFormat::Hex => Some(16),
+        }
+    }
+
+    fn prefix(self) -> &'static str {
+        match self {
+            NumberFormat::Decimal => "",
+            NumberFormat::Binary => "0b",
+            NumberFormat::Octal => "0o",
+            NumberFormat::Hex => "0x",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum ValueKind {
     FunctionRef(Name),
     Number(BigDecimal),
+    Rational(BigRational),
+    Complex(Complex<BigDecimal>),
     Bool(bool),
 }
 
+/// Views a `Number`/`Rational`/`Complex` kind as a complex number with a
+/// (possibly zero) imaginary part. Returns `None` for non-numeric kinds.
+fn to_complex(kind: &ValueKind) -> Option<Complex<BigDecimal>> {
+    match kind {
+        ValueKind::Number(n) => Some(Complex::new(n.clone(), BigDecimal::from(0))),
+        ValueKind::Rational(r) => Some(Complex::new(rational_to_decimal(r), BigDecimal::from(0))),
+        ValueKind::Complex(c) => Some(c.clone()),
+        _ => None,
+    }
+}
+
+impl ValueKind {
+    /// Multiplies two numeric kinds, promoting to `Complex` as soon as
+    /// either side has one, and otherwise keeping `Rational`/`Number` as
+    /// exact as the inputs allow. Returns `None` for non-numeric kinds.
+    pub fn multiply(&self, other: &ValueKind) -> Option<ValueKind> {
+        match (self, other) {
+            (ValueKind::Complex(_), _) | (_, ValueKind::Complex(_)) => {
+                Some(ValueKind::Complex(to_complex(self)? * to_complex(other)?))
+            }
+            (ValueKind::Rational(a), ValueKind::Rational(b)) => Some(ValueKind::Rational(a * b)),
+            (ValueKind::Rational(a), ValueKind::Number(b))
+            | (ValueKind::Number(b), ValueKind::Rational(a)) => {
+                Some(ValueKind::Number(rational_to_decimal(a) * b))
+            }
+            (ValueKind::Number(a), ValueKind::Number(b)) => Some(ValueKind::Number(a * b)),
+            _ => None,
+        }
+    }
+
+    /// Divides two numeric kinds, promoting to `Complex` as soon as either
+    /// side has one. Dividing two integer-valued `Number`s produces an
+    /// exact `Rational` instead of a lossy decimal.
+    pub fn divide(&self, other: &ValueKind) -> Option<ValueKind> {
+        match (self, other) {
+            (ValueKind::Complex(_), _) | (_, ValueKind::Complex(_)) => {
+                Some(ValueKind::Complex(to_complex(self)? / to_complex(other)?))
+            }
+            (ValueKind::Rational(a), ValueKind::Rational(b)) => Some(ValueKind::Rational(a / b)),
+            (ValueKind::Rational(a), ValueKind::Number(b)) => {
+                Some(ValueKind::Number(rational_to_decimal(a) / b))
+            }
+            (ValueKind::Number(a), ValueKind::Rational(b)) => {
+                Some(ValueKind::Number(a / rational_to_decimal(b)))
+            }
+            (ValueKind::Number(a), ValueKind::Number(b)) => match (to_bigint(a), to_bigint(b)) {
+                (Some(a_int), Some(b_int)) if !b_int.is_zero() => {
+                    Some(ValueKind::Rational(BigRational::new(a_int, b_int)))
+                }
+                _ => Some(ValueKind::Number(a / b)),
+            },
+            _ => None,
+        }
+    }
+
+    /// Raises a numeric kind to an integer power by repeated
+    /// multiplication/division, mirroring [`Unit::pow`].
+    pub fn pow(&self, n: isize) -> Option<ValueKind> {
+        let is_neg = n.is_negative();
+        let n_add = n.abs();
+
+        let mut result = multiplicative_identity(self)?;
+        for _ in 0..n_add {
+            result = result.multiply(self)?;
+        }
+
+        if is_neg {
+            multiplicative_identity(self)?.divide(&result)
+        } else {
+            Some(result)
+        }
+    }
+}
+
+/// The multiplicative identity for a numeric kind, in the same kind as
+/// `kind` so that e.g. `pow` on a `Rational` doesn't collapse through
+/// `Number` on its very first iteration. `None` for non-numeric kinds.
+fn multiplicative_identity(kind: &ValueKind) -> Option<ValueKind> {
+    match kind {
+        ValueKind::Number(_) => Some(ValueKind::Number(BigDecimal::from(1))),
+        ValueKind::Rational(_) => Some(ValueKind::Rational(BigRational::one())),
+        ValueKind::Complex(_) => Some(ValueKind::Complex(Complex::new(
+            BigDecimal::from(1),
+            BigDecimal::from(0),
+        ))),
+        _ => None,
+    }
+}
+
+fn format_decimal(num: &BigDecimal) -> String {
+    let (int, dec, exp) = dec_in_scientific_notation(&num.normalized());
+
+    let exp_str = if exp == 0 {
+        "".to_string()
+    } else {
+        format!("x10^{}", exp)
+    };
+
+    if (0..4).contains(&exp) {
+        if dec.len() < exp as _ {
+            format!("{:.prec$}", num, prec = 0)
+        } else {
+            format!("{:.prec$}", num, prec = dec.len().min(4) - exp as usize)
+        }
+    } else if (-3..0).contains(&exp) {
+        format!(
+            "{:.prec$}",
+            num,
+            prec = (dec.len() + (-exp) as usize).min(4)
+        )
+    } else if dec.is_empty() {
+        format!("{}{}", int, exp_str)
+    } else {
+        let dec = max_precision(&dec, 3);
+        format!("{}.{}{}", int, dec, exp_str)
+    }
+}
+
+/// Writes `s` into `f`, honoring the formatter's `width`/`fill`/`align`
+/// flags (right-aligned by default, matching how numbers are usually
+/// displayed).
+fn write_padded(f: &mut std::fmt::Formatter<'_>, s: &str) -> std::fmt::Result {
+    let len = s.chars().count();
+    let width = match f.width() {
+        Some(width) if width > len => width,
+        _ => return f.write_str(s),
+    };
+
+    let fill = f.fill();
+    let pad = width - len;
+    let align = f.align().unwrap_or(std::fmt::Alignment::Right);
+
+    match align {
+        std::fmt::Alignment::Left => {
+            f.write_str(s)?;
+            for _ in 0..pad {
+                f.write_char(fill)?;
+            }
+            Ok(())
+        }
+        std::fmt::Alignment::Right => {
+            for _ in 0..pad {
+                f.write_char(fill)?;
+            }
+            f.write_str(s)
+        }
+        std::fmt::Alignment::Center => {
+            let left = pad / 2;
+            let right = pad - left;
+            for _ in 0..left {
+                f.write_char(fill)?;
+            }
+            f.write_str(s)?;
+            for _ in 0..right {
+                f.write_char(fill)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn rational_to_decimal(rat: &BigRational) -> BigDecimal {
+    BigDecimal::new(rat.numer().clone(), 0) / BigDecimal::new(rat.denom().clone(), 0)
+}
+
+/// Converts `num` to a `BigInt` if it has no nonzero fractional part,
+/// `None` otherwise.
+fn to_bigint(num: &BigDecimal) -> Option<BigInt> {
+    let (digits, scale) = num.normalized().as_bigint_and_exponent();
+
+    if scale > 0 {
+        let divisor = BigInt::from(10).pow(scale as u32);
+        if !(&digits % &divisor).is_zero() {
+            return None;
+        }
+        Some(digits / divisor)
+    } else {
+        Some(digits * BigInt::from(10).pow((-scale) as u32))
+    }
+}
+
+/// Renders the integer value of `num` in the given `radix`, prefixed with
+/// `prefix` (e.g. `"0x"`); zero is rendered as a bare `"0"` without the
+/// prefix. Returns `None` if `num` has a nonzero fractional part, since
+/// radix rendering only makes sense for whole numbers.
+fn format_radix(num: &BigDecimal, radix: u32, prefix: &str) -> Option<String> {
+    let magnitude = to_bigint(num)?;
+
+    if magnitude.is_zero() {
+        return Some("0".to_string());
+    }
+
+    let sign = if magnitude.is_negative() { "-" } else { "" };
+    Some(format!(
+        "{}{}{}",
+        sign,
+        prefix,
+        magnitude.abs().to_str_radix(radix)
+    ))
+}
+
+/// Renders `num` with exactly `precision` fractional digits when given,
+/// falling back to the scientific-notation heuristics otherwise. Shared by
+/// every numeric `ValueKind` so a requested precision applies uniformly.
+fn format_component(num: &BigDecimal, precision: Option<usize>) -> String {
+    match precision {
+        Some(prec) => format!("{:.prec$}", num, prec = prec),
+        None => format_decimal(num),
+    }
+}
+
 impl Display for ValueKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ValueKind::FunctionRef(name) => f.write_fmt(format_args!("<function {}>", name)),
-            ValueKind::Number(num) => {
-                let (int, dec, exp) = dec_in_scientific_notation(&num.normalized());
-
-                let exp_str = if exp == 0 {
-                    "".to_string()
+            ValueKind::Number(num) => write_padded(f, &format_component(num, f.precision())),
+            ValueKind::Rational(rat) => {
+                let s = if let Some(prec) = f.precision() {
+                    format_component(&rational_to_decimal(rat), Some(prec))
+                } else if rat.denom().is_one() {
+                    format!("{}", rat.numer())
                 } else {
-                    format!("x10^{}", exp)
+                    format!("{}/{}", rat.numer(), rat.denom())
                 };
+                write_padded(f, &s)
+            }
+            ValueKind::Complex(c) => {
+                let re_zero = c.re.is_zero();
+                let im_zero = c.im.is_zero();
+                let prec = f.precision();
 
-                if (0..4).contains(&exp) {
-                    if dec.len() < exp as _ {
-                        f.write_fmt(format_args!("{:.prec$}", num, prec = 0))
-                    } else {
-                        f.write_fmt(format_args!(
-                            "{:.prec$}",
-                            num,
-                            prec = dec.len().min(4) - exp as usize
-                        ))
-                    }
-                } else if (-3..0).contains(&exp) {
-                    f.write_fmt(format_args!(
-                        "{:.prec$}",
-                        num,
-                        prec = (dec.len() + (-exp) as usize).min(4)
-                    ))
-                } else if dec.is_empty() {
-                    f.write_fmt(format_args!("{}{}", int, exp_str))
+                let s = if im_zero {
+                    format_component(&c.re, prec)
+                } else if re_zero {
+                    format!("{}i", format_component(&c.im, prec))
+                } else if c.im.is_negative() {
+                    format!(
+                        "{} - {}i",
+                        format_component(&c.re, prec),
+                        format_component(&-&c.im, prec)
+                    )
                 } else {
-                    let dec = max_precision(&dec, 3);
-                    f.write_fmt(format_args!("{}.{}{}", int, dec, exp_str))
-                }
+                    format!(
+                        "{} + {}i",
+                        format_component(&c.re, prec),
+                        format_component(&c.im, prec)
+                    )
+                };
+                write_padded(f, &s)
             }
             ValueKind::Bool(b) => b.fmt(f),
         }
     }
 }
 
+impl Value {
+    pub fn new(kind: ValueKind, unit: Unit) -> Self {
+        Self {
+            kind,
+            unit,
+            format: NumberFormat::default(),
+        }
+    }
+
+    pub fn multiply(&self, other: &Value) -> Option<Value> {
+        Some(Value {
+            kind: self.kind.multiply(&other.kind)?,
+            unit: self.unit.multiply(&other.unit),
+            format: self.format,
+        })
+    }
+
+    pub fn divide(&self, other: &Value) -> Option<Value> {
+        Some(Value {
+            kind: self.kind.divide(&other.kind)?,
+            unit: self.unit.divide(&other.unit),
+            format: self.format,
+        })
+    }
+
+    pub fn pow(&self, n: isize) -> Option<Value> {
+        Some(Value {
+            kind: self.kind.pow(n)?,
+            unit: self.unit.pow(n),
+            format: self.format,
+        })
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let radix_formatted = self.format.radix().and_then(|radix| {
+            let num = match &self.kind {
+                ValueKind::Number(num) => Some(num.clone()),
+                ValueKind::Rational(rat) if rat.denom().is_one() => {
+                    Some(BigDecimal::from(rat.numer().clone()))
+                }
+                _ => None,
+            }?;
+
+            format_radix(&num, radix, self.format.prefix())
+        });
+
+        // Rendered without the outer width/fill/align, which apply once to
+        // the number+unit string as a whole further down.
+        let body = match radix_formatted {
+            Some(s) => s,
+            None => match f.precision() {
+                Some(prec) => format!("{:.prec$}", self.kind, prec = prec),
+                None => format!("{}", self.kind),
+            },
+        };
+
+        let s = if self.unit.parts.is_empty() {
+            body
+        } else {
+            format!("{} {}", body, self.unit)
+        };
+
+        write_padded(f, &s)
+    }
+}
+
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
 pub struct Unit {
     parts: BTreeMap<Name, isize>,
@@ -161,4 +488,46 @@ impl Display for Unit {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rational(numer: i64, denom: i64) -> ValueKind {
+        ValueKind::Rational(BigRational::new(BigInt::from(numer), BigInt::from(denom)))
+    }
+
+    #[test]
+    fn rational_times_rational_stays_rational() {
+        let product = rational(1, 3).multiply(&rational(1, 2)).unwrap();
+        assert_eq!(product.to_string(), "1/6");
+    }
+
+    #[test]
+    fn rational_divided_by_rational_stays_rational() {
+        let quotient = rational(1, 3).divide(&rational(1, 2)).unwrap();
+        assert_eq!(quotient.to_string(), "2/3");
+    }
+
+    #[test]
+    fn integer_division_produces_exact_rational() {
+        let quotient = ValueKind::Number(BigDecimal::from(1))
+            .divide(&ValueKind::Number(BigDecimal::from(3)))
+            .unwrap();
+        assert_eq!(quotient.to_string(), "1/3");
+    }
+
+    #[test]
+    fn rational_to_the_first_power_round_trips_exactly() {
+        let result = rational(1, 3).pow(1).unwrap();
+        assert_eq!(result.to_string(), "1/3");
+    }
+
+    #[test]
+    fn rational_to_the_zeroth_power_is_rational_one() {
+        let result = rational(1, 3).pow(0).unwrap();
+        assert_eq!(result, rational(1, 1));
+        assert_eq!(result.to_string(), "1");
+    }
+}